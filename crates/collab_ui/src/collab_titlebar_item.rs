@@ -1,38 +1,30 @@
+use crate::contacts_popover::ContactsPopover;
+use call::{ActiveCall, ParticipantLocation};
 use client::UserStore;
 use gpui::{
-    actions, div, px, Action, AppContext, Element, InteractiveElement, IntoElement, Model,
-    ParentElement, Render, StatefulInteractiveElement, Styled, Subscription, View, ViewContext,
-    VisualContext, WeakView, WindowBounds,
+    actions, div, Action, AppContext, Element, InteractiveElement, IntoElement, Model,
+    ParentElement, Render, StatefulInteractiveElement, Styled, Subscription, ViewContext,
+    VisualContext, WindowBounds,
 };
-use project::{Project, RepositoryEntry};
-use recent_projects::RecentProjects;
 use std::env;
 use theme::ActiveTheme;
+use title_bar::{TitleBar, WindowControlsPlacement, TRAFFIC_LIGHT_PADDING};
 use ui::{
     h_flex, popover_menu, prelude::*, Avatar, Button, ButtonLike, ButtonStyle, ContextMenu, Icon,
-    IconName, Tooltip,
+    IconButton, IconName, IconSize, Tooltip,
 };
 use util::ResultExt;
-use vcs_menu::{build_branch_list, BranchList, OpenRecent as ToggleVcsMenu};
 use workspace::{titlebar_height, Workspace};
 
-const MAX_PROJECT_NAME_LENGTH: usize = 40;
-const MAX_BRANCH_NAME_LENGTH: usize = 40;
-
-actions!(collab, [ToggleUserMenu, ToggleProjectMenu, SwitchBranch]);
+actions!(collab, [ShareProject, UnshareProject, ToggleScreenSharing]);
 
 pub fn init(cx: &mut AppContext) {
-    cx.observe_new_views(|workspace: &mut Workspace, cx| {
-        let titlebar_item = cx.new_view(|cx| CollabTitlebarItem::new(workspace, cx));
-        workspace.set_titlebar_item(titlebar_item.into(), cx)
-    })
-    .detach();
+    title_bar::init_titlebar_item(cx, CollabTitlebarItem::new);
 }
 
 pub struct CollabTitlebarItem {
-    project: Model<Project>,
+    title_bar: TitleBar,
     user_store: Model<UserStore>,
-    workspace: WeakView<Workspace>,
     _subscriptions: Vec<Subscription>,
 }
 
@@ -44,12 +36,13 @@ impl Render for CollabTitlebarItem {
             .w_full()
             .h(titlebar_height(cx))
             .map(|this| {
-                if matches!(cx.window_bounds(), WindowBounds::Fullscreen) {
-                    this.pl_2()
-                } else {
+                let is_fullscreen = matches!(cx.window_bounds(), WindowBounds::Fullscreen);
+                if cfg!(target_os = "macos") && !is_fullscreen {
                     // Use pixels here instead of a rem-based size because the macOS traffic
                     // lights are a static size, and don't scale with the rest of the UI.
-                    this.pl(px(80.))
+                    this.pl(TRAFFIC_LIGHT_PADDING)
+                } else {
+                    this.pl_2()
                 }
             })
             .bg(cx.theme().colors().title_bar_background)
@@ -62,12 +55,32 @@ impl Render for CollabTitlebarItem {
             .child(
                 h_flex()
                     .gap_1()
+                    .when(
+                        TitleBar::window_controls_placement(cx)
+                            == Some(WindowControlsPlacement::Leading),
+                        |this| this.children(self.title_bar.render_window_controls(cx)),
+                    )
                     .children(self.render_project_host(cx))
-                    .child(self.render_project_name(cx))
-                    .children(self.render_project_branch(cx)),
+                    .child(self.title_bar.render_project_name(cx))
+                    .children(self.title_bar.render_project_branch(cx))
+                    .children(self.render_collaborators(cx)),
             )
             // right side
-            .child(h_flex().gap_1().pr_1().child(self.render_whats_up(cx)))
+            .child(
+                h_flex()
+                    .gap_1()
+                    .pr_1()
+                    .children(self.render_share_button(cx))
+                    .children(self.render_toggle_screen_sharing_button(cx))
+                    .child(self.render_whats_up(cx))
+                    .children(self.render_contacts_button(cx))
+                    .child(self.render_user_menu_button(cx))
+                    .when(
+                        TitleBar::window_controls_placement(cx)
+                            == Some(WindowControlsPlacement::Trailing),
+                        |this| this.children(self.title_bar.render_window_controls(cx)),
+                    ),
+            )
     }
 }
 
@@ -83,10 +96,14 @@ impl CollabTitlebarItem {
         );
         subscriptions.push(cx.observe(&project, |_, _, cx| cx.notify()));
         subscriptions.push(cx.observe(&user_store, |_, _, cx| cx.notify()));
+        subscriptions.push(cx.observe(&ActiveCall::global(cx), |_, _, cx| cx.notify()));
+
+        cx.on_action(cx.listener(Self::toggle_project_share));
+        cx.on_action(cx.listener(Self::unshare_project));
+        cx.on_action(cx.listener(Self::toggle_screen_sharing));
 
         Self {
-            workspace: workspace.weak_handle(),
-            project,
+            title_bar: TitleBar::new(project, workspace.weak_handle()),
             user_store,
             _subscriptions: subscriptions,
         }
@@ -96,7 +113,7 @@ impl CollabTitlebarItem {
     // render_project_owner -> resolve if you are in a room -> Option<foo>
 
     pub fn render_project_host(&self, cx: &mut ViewContext<Self>) -> Option<impl IntoElement> {
-        let host = self.project.read(cx).host()?;
+        let host = self.title_bar.project().read(cx).host()?;
         let host_user = self.user_store.read(cx).get_cached_user(host.user_id)?;
         let participant_index = self
             .user_store
@@ -120,7 +137,8 @@ impl CollabTitlebarItem {
                 .on_click({
                     let host_peer_id = host.peer_id;
                     cx.listener(move |this, _, cx| {
-                        this.workspace
+                        this.title_bar
+                            .workspace()
                             .update(cx, |workspace, cx| {
                                 workspace.follow(host_peer_id, cx);
                             })
@@ -130,88 +148,195 @@ impl CollabTitlebarItem {
         )
     }
 
-    pub fn render_project_name(&self, cx: &mut ViewContext<Self>) -> impl Element {
-        let name = {
-            let mut names = self.project.read(cx).visible_worktrees(cx).map(|worktree| {
-                let worktree = worktree.read(cx);
-                worktree.root_name()
-            });
+    pub fn render_collaborators(&self, cx: &mut ViewContext<Self>) -> Option<impl IntoElement> {
+        let room = ActiveCall::global(cx).read(cx).room()?.read(cx);
+        let participant_indices = self.user_store.read(cx).participant_indices();
 
-            names.next()
-        };
-        let is_project_selected = name.is_some();
-        let name = if let Some(name) = name {
-            util::truncate_and_trailoff(name, MAX_PROJECT_NAME_LENGTH)
+        let mut participants = room.remote_participants().values().collect::<Vec<_>>();
+        participants.sort_by_key(|participant| {
+            participant_indices
+                .get(&participant.user.id)
+                .map(|index| index.0)
+        });
+
+        Some(
+            h_flex()
+                .ml_1()
+                .children(
+                    participants
+                        .into_iter()
+                        .filter_map(|participant| self.render_collaborator(participant, cx)),
+                ),
+        )
+    }
+
+    fn render_collaborator(
+        &self,
+        participant: &call::RemoteParticipant,
+        cx: &mut ViewContext<Self>,
+    ) -> Option<impl IntoElement> {
+        let user = participant.user.clone();
+        let peer_id = participant.peer_id;
+        let participant_index = self
+            .user_store
+            .read(cx)
+            .participant_indices()
+            .get(&user.id)
+            .copied()?;
+        let is_present = matches!(participant.location, ParticipantLocation::SharedProject { .. });
+        let is_being_followed = self
+            .title_bar
+            .workspace()
+            .upgrade()
+            .is_some_and(|workspace| workspace.read(cx).is_being_followed(peer_id));
+        let tooltip_text = if is_being_followed {
+            format!("Following {}", user.github_login)
         } else {
-            "Open recent project".to_string()
+            match &participant.location {
+                ParticipantLocation::SharedProject { project_id } => self
+                    .title_bar
+                    .project()
+                    .read(cx)
+                    .visible_worktrees(cx)
+                    .next()
+                    .map(|worktree| {
+                        format!(
+                            "{} is active in {}",
+                            user.github_login,
+                            worktree.read(cx).root_name()
+                        )
+                    })
+                    .unwrap_or_else(|| {
+                        format!("{} is active in project {}", user.github_login, project_id)
+                    }),
+                ParticipantLocation::UnsharedProject => {
+                    format!("{} is viewing an unshared project", user.github_login)
+                }
+                ParticipantLocation::External => {
+                    format!("{} is not in a shared project", user.github_login)
+                }
+            }
         };
 
-        let workspace = self.workspace.clone();
-        popover_menu("project_name_trigger")
-            .trigger(
-                Button::new("project_name_trigger", name)
-                    .when(!is_project_selected, |b| b.color(Color::Muted))
+        Some(
+            div().ml_neg_1().child(
+                ButtonLike::new(("collaborator", peer_id.as_u64() as usize))
+                    .child(
+                        Avatar::new(user.avatar_uri.clone())
+                            .when(!is_present, |avatar| avatar.grayscale(true)),
+                    )
                     .style(ButtonStyle::Subtle)
-                    .label_size(LabelSize::Small)
-                    .tooltip(move |cx| Tooltip::text("Recent Projects", cx)),
-            )
-            .menu(move |cx| Some(Self::render_project_popover(workspace.clone(), cx)))
+                    .color(Color::Player(participant_index.0))
+                    .tooltip(move |cx| Tooltip::text(tooltip_text.clone(), cx))
+                    .on_click(cx.listener(move |this, _, cx| {
+                        this.title_bar
+                            .workspace()
+                            .update(cx, |workspace, cx| {
+                                workspace.follow(peer_id, cx);
+                            })
+                            .log_err();
+                    })),
+            ),
+        )
     }
 
-    pub fn render_project_branch(&self, cx: &mut ViewContext<Self>) -> Option<impl Element> {
-        let entry = {
-            let mut names_and_branches =
-                self.project.read(cx).visible_worktrees(cx).map(|worktree| {
-                    let worktree = worktree.read(cx);
-                    worktree.root_git_entry()
-                });
+    fn is_call_host(&self, cx: &ViewContext<Self>) -> bool {
+        ActiveCall::global(cx)
+            .read(cx)
+            .room()
+            .is_some_and(|_| self.title_bar.project().read(cx).is_local())
+    }
+
+    fn toggle_project_share(&mut self, _: &ShareProject, cx: &mut ViewContext<Self>) {
+        let active_call = ActiveCall::global(cx);
+        if self.title_bar.project().read(cx).is_shared() {
+            active_call.update(cx, |call, cx| {
+                call.unshare_project(self.title_bar.project().clone(), cx)
+                    .log_err();
+            });
+        } else {
+            active_call
+                .update(cx, |call, cx| {
+                    call.share_project(self.title_bar.project().clone(), cx)
+                })
+                .detach_and_log_err(cx);
+        }
+    }
+
+    fn unshare_project(&mut self, _: &UnshareProject, cx: &mut ViewContext<Self>) {
+        ActiveCall::global(cx).update(cx, |call, cx| {
+            call.unshare_project(self.title_bar.project().clone(), cx)
+                .log_err();
+        });
+    }
 
-            names_and_branches.next().flatten()
+    fn toggle_screen_sharing(&mut self, _: &ToggleScreenSharing, cx: &mut ViewContext<Self>) {
+        let Some(room) = ActiveCall::global(cx).read(cx).room().cloned() else {
+            return;
         };
-        let workspace = self.workspace.upgrade()?;
-        let branch_name = entry
-            .as_ref()
-            .and_then(RepositoryEntry::branch)
-            .map(|branch| util::truncate_and_trailoff(&branch, MAX_BRANCH_NAME_LENGTH))?;
+        room.update(cx, |room, cx| {
+            if room.is_screen_sharing() {
+                room.unshare_screen(cx).log_err();
+            } else {
+                room.share_screen(cx).detach_and_log_err(cx);
+            }
+        });
+    }
+
+    pub fn render_share_button(&self, cx: &mut ViewContext<Self>) -> Option<impl IntoElement> {
+        if !self.is_call_host(cx) {
+            return None;
+        }
+        let is_shared = self.title_bar.project().read(cx).is_shared();
         Some(
-            popover_menu("project_branch_trigger")
-                .trigger(
-                    Button::new("project_branch_trigger", branch_name)
-                        .color(Color::Muted)
-                        .style(ButtonStyle::Subtle)
-                        .label_size(LabelSize::Small)
-                        .tooltip(move |cx| {
-                            Tooltip::with_meta(
-                                "Recent Branches",
-                                Some(&ToggleVcsMenu),
-                                "Local branches only",
-                                cx,
-                            )
-                        }),
+            Button::new(
+                "toggle_project_share",
+                if is_shared { "Unshare" } else { "Share" },
+            )
+            .style(ButtonStyle::Subtle)
+            .label_size(LabelSize::Small)
+            .when(is_shared, |this| this.color(Color::Accent))
+            .tooltip(move |cx| {
+                Tooltip::text(
+                    if is_shared {
+                        "Stop sharing this project with collaborators"
+                    } else {
+                        "Share this project with the people in your call"
+                    },
+                    cx,
                 )
-                .menu(move |cx| Self::render_vcs_popover(workspace.clone(), cx)),
+            })
+            .on_click(cx.listener(|this, _, cx| {
+                this.toggle_project_share(&ShareProject, cx);
+            })),
         )
     }
 
-    pub fn render_vcs_popover(
-        workspace: View<Workspace>,
-        cx: &mut WindowContext<'_>,
-    ) -> Option<View<BranchList>> {
-        let view = build_branch_list(workspace, cx).log_err()?;
-        let focus_handle = view.focus_handle(cx);
-        cx.focus(&focus_handle);
-        Some(view)
-    }
-
-    pub fn render_project_popover(
-        workspace: WeakView<Workspace>,
-        cx: &mut WindowContext<'_>,
-    ) -> View<RecentProjects> {
-        let view = RecentProjects::open_popover(workspace, cx);
-
-        let focus_handle = view.focus_handle(cx);
-        cx.focus(&focus_handle);
-        view
+    pub fn render_toggle_screen_sharing_button(
+        &self,
+        cx: &mut ViewContext<Self>,
+    ) -> Option<impl IntoElement> {
+        let room = ActiveCall::global(cx).read(cx).room()?.read(cx);
+        let is_screen_sharing = room.is_screen_sharing();
+        Some(
+            IconButton::new("toggle_screen_sharing", IconName::Screen)
+                .style(ButtonStyle::Subtle)
+                .icon_size(IconSize::Small)
+                .selected(is_screen_sharing)
+                .tooltip(move |cx| {
+                    Tooltip::text(
+                        if is_screen_sharing {
+                            "Stop sharing your screen"
+                        } else {
+                            "Share your screen"
+                        },
+                        cx,
+                    )
+                })
+                .on_click(cx.listener(|this, _, cx| {
+                    this.toggle_screen_sharing(&ToggleScreenSharing, cx);
+                })),
+        )
     }
 
     pub fn render_whats_up(&mut self, _cx: &mut ViewContext<Self>) -> Div {
@@ -221,6 +346,26 @@ impl CollabTitlebarItem {
             .child(Label::new(format!("what's up, {}", user)).size(LabelSize::Small))
     }
 
+    pub fn render_contacts_button(&mut self, cx: &mut ViewContext<Self>) -> Option<impl Element> {
+        self.user_store.read(cx).current_user()?;
+        let user_store = self.user_store.clone();
+        let workspace = self.title_bar.workspace().clone();
+        Some(
+            popover_menu("contacts-menu")
+                .menu(move |cx| {
+                    Some(cx.new_view(|cx| {
+                        ContactsPopover::new(user_store.clone(), workspace.clone(), cx)
+                    }))
+                })
+                .trigger(
+                    ButtonLike::new("contacts-menu")
+                        .child(Icon::new(IconName::Plus).color(Color::Muted))
+                        .style(ButtonStyle::Subtle)
+                        .tooltip(move |cx| Tooltip::text("Contacts", cx)),
+                ),
+        )
+    }
+
     pub fn render_user_menu_button(&mut self, cx: &mut ViewContext<Self>) -> impl Element {
         if let Some(user) = self.user_store.read(cx).current_user() {
             popover_menu("user-menu")
@@ -1,7 +1,9 @@
 mod collab_titlebar_item;
+mod contacts_popover;
 mod panel_settings;
 
 pub use collab_titlebar_item::CollabTitlebarItem;
+pub use contacts_popover::ContactsPopover;
 use gpui::AppContext;
 pub use panel_settings::NotificationPanelSettings;
 
@@ -0,0 +1,306 @@
+use call::ActiveCall;
+use client::{Contact, User, UserStore};
+use fuzzy::{match_strings, StringMatch, StringMatchCandidate};
+use gpui::{
+    AppContext, DismissEvent, EventEmitter, FocusHandle, FocusableView, Model, Render, Task, View,
+    ViewContext, WeakView,
+};
+use picker::{Picker, PickerDelegate};
+use std::sync::Arc;
+use ui::{h_flex, prelude::*, v_flex, Avatar, IconButton, IconName, IconSize, Label, ListItem};
+use util::ResultExt;
+use workspace::Workspace;
+
+pub struct ContactsPopover {
+    picker: View<Picker<ContactsPopoverDelegate>>,
+}
+
+impl ContactsPopover {
+    pub fn new(
+        user_store: Model<UserStore>,
+        workspace: WeakView<Workspace>,
+        cx: &mut ViewContext<Self>,
+    ) -> Self {
+        let delegate = ContactsPopoverDelegate::new(user_store.clone(), workspace);
+        let picker = cx.new_view(|cx| {
+            // Contacts and incoming requests can change underneath us (an
+            // accept/decline we sent resolves, or the other side acts
+            // first), so re-run the search whenever the store updates
+            // instead of only on the next keystroke.
+            cx.observe(&user_store, |picker, _, cx| {
+                picker.delegate.requery(cx);
+            })
+            .detach();
+            Picker::uniform_list(delegate, cx)
+        });
+        Self { picker }
+    }
+}
+
+impl FocusableView for ContactsPopover {
+    fn focus_handle(&self, cx: &AppContext) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl EventEmitter<DismissEvent> for ContactsPopover {}
+
+impl Render for ContactsPopover {
+    fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+        v_flex().w_72().child(self.picker.clone())
+    }
+}
+
+/// A row in the contacts list: either someone who has sent us a contact
+/// request and is waiting on an accept/decline, or an existing contact we
+/// can call.
+enum ContactEntry {
+    IncomingRequest(Arc<User>),
+    Contact(Arc<Contact>),
+}
+
+impl ContactEntry {
+    fn github_login(&self) -> &str {
+        match self {
+            ContactEntry::IncomingRequest(user) => &user.github_login,
+            ContactEntry::Contact(contact) => &contact.user.github_login,
+        }
+    }
+}
+
+pub struct ContactsPopoverDelegate {
+    user_store: Model<UserStore>,
+    workspace: WeakView<Workspace>,
+    entries: Vec<ContactEntry>,
+    matches: Vec<StringMatch>,
+    selected_index: usize,
+    last_query: String,
+}
+
+impl ContactsPopoverDelegate {
+    fn new(user_store: Model<UserStore>, workspace: WeakView<Workspace>) -> Self {
+        Self {
+            user_store,
+            workspace,
+            entries: Vec::new(),
+            matches: Vec::new(),
+            selected_index: 0,
+            last_query: String::new(),
+        }
+    }
+
+    /// Re-runs the last search, so a row that's no longer current (e.g. a
+    /// request we just accepted or declined) drops out of the list without
+    /// waiting for the user to touch the search box.
+    fn requery(&mut self, cx: &mut ViewContext<Picker<Self>>) {
+        self.update_matches(self.last_query.clone(), cx).detach();
+    }
+
+    fn call(&self, contact: &Contact, cx: &mut WindowContext) {
+        if !contact.online {
+            return;
+        }
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
+        let user_id = contact.user.id;
+        workspace.update(cx, |_, cx| {
+            let active_call = ActiveCall::global(cx);
+            let already_in_call = active_call.read(cx).room().is_some_and(|room| {
+                room.read(cx)
+                    .remote_participants()
+                    .values()
+                    .any(|participant| participant.user.id == user_id)
+            });
+            if already_in_call {
+                return;
+            }
+
+            active_call
+                .update(cx, |call, cx| call.invite(user_id, None, cx))
+                .detach_and_log_err(cx);
+        });
+    }
+
+    fn respond_to_contact_request(
+        &mut self,
+        user_id: u64,
+        accept: bool,
+        cx: &mut ViewContext<Picker<Self>>,
+    ) {
+        self.user_store
+            .update(cx, |store, cx| {
+                store.respond_to_contact_request(user_id, accept, cx)
+            })
+            .detach_and_log_err(cx);
+    }
+}
+
+impl PickerDelegate for ContactsPopoverDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _cx: &mut WindowContext) -> Arc<str> {
+        Arc::from("Search contacts...")
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(&mut self, ix: usize, _cx: &mut ViewContext<Picker<Self>>) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(&mut self, query: String, cx: &mut ViewContext<Picker<Self>>) -> Task<()> {
+        self.last_query = query.clone();
+        let user_store = self.user_store.read(cx);
+        self.entries = user_store
+            .incoming_contact_requests()
+            .iter()
+            .cloned()
+            .map(ContactEntry::IncomingRequest)
+            .chain(
+                user_store
+                    .contacts()
+                    .iter()
+                    .cloned()
+                    .map(ContactEntry::Contact),
+            )
+            .collect();
+        let candidates = self
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(ix, entry)| StringMatchCandidate::new(ix, entry.github_login().to_string()))
+            .collect::<Vec<_>>();
+
+        cx.spawn(|this, mut cx| async move {
+            let matches = if query.is_empty() {
+                candidates
+                    .into_iter()
+                    .map(|candidate| StringMatch {
+                        candidate_id: candidate.id,
+                        score: 0.,
+                        positions: Vec::new(),
+                        string: candidate.string,
+                    })
+                    .collect()
+            } else {
+                match_strings(
+                    &candidates,
+                    &query,
+                    false,
+                    100,
+                    &Default::default(),
+                    cx.background_executor().clone(),
+                )
+                .await
+            };
+
+            this.update(&mut cx, |this, cx| {
+                this.delegate.matches = matches;
+                this.delegate.selected_index = 0;
+                cx.notify();
+            })
+            .log_err();
+        })
+    }
+
+    fn confirm(&mut self, _secondary: bool, cx: &mut ViewContext<Picker<Self>>) {
+        let Some(mat) = self.matches.get(self.selected_index) else {
+            return;
+        };
+        match &self.entries[mat.candidate_id] {
+            ContactEntry::IncomingRequest(user) => {
+                let user_id = user.id;
+                // Leave the popover open so other pending requests can still
+                // be accepted or declined in the same session.
+                self.respond_to_contact_request(user_id, true, cx);
+                return;
+            }
+            ContactEntry::Contact(contact) => {
+                let contact = contact.clone();
+                self.call(&contact, cx);
+            }
+        }
+        cx.emit(DismissEvent);
+    }
+
+    fn dismissed(&mut self, _cx: &mut ViewContext<Picker<Self>>) {}
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        cx: &mut ViewContext<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let mat = self.matches.get(ix)?;
+        match self.entries.get(mat.candidate_id)? {
+            ContactEntry::IncomingRequest(user) => {
+                let accept_user_id = user.id;
+                let decline_user_id = user.id;
+                Some(
+                    ListItem::new(ix).selected(selected).child(
+                        h_flex()
+                            .w_full()
+                            .justify_between()
+                            .gap_2()
+                            .child(
+                                h_flex()
+                                    .gap_2()
+                                    .child(Avatar::new(user.avatar_uri.clone()))
+                                    .child(Label::new(user.github_login.clone())),
+                            )
+                            .child(
+                                h_flex()
+                                    .gap_1()
+                                    .child(
+                                        IconButton::new(
+                                            ("accept-contact-request", ix),
+                                            IconName::Check,
+                                        )
+                                        .icon_size(IconSize::Small)
+                                        .on_click(cx.listener(move |picker, _, cx| {
+                                            picker.delegate.respond_to_contact_request(
+                                                accept_user_id,
+                                                true,
+                                                cx,
+                                            );
+                                        })),
+                                    )
+                                    .child(
+                                        IconButton::new(
+                                            ("decline-contact-request", ix),
+                                            IconName::Close,
+                                        )
+                                        .icon_size(IconSize::Small)
+                                        .on_click(cx.listener(move |picker, _, cx| {
+                                            picker.delegate.respond_to_contact_request(
+                                                decline_user_id,
+                                                false,
+                                                cx,
+                                            );
+                                        })),
+                                    ),
+                            ),
+                    ),
+                )
+            }
+            ContactEntry::Contact(contact) => Some(
+                ListItem::new(ix)
+                    .selected(selected)
+                    .child(
+                        h_flex()
+                            .gap_2()
+                            .child(Avatar::new(contact.user.avatar_uri.clone()))
+                            .child(Label::new(contact.user.github_login.clone()))
+                            .when(!contact.online, |this| this.opacity(0.5)),
+                    ),
+            ),
+        }
+    }
+}
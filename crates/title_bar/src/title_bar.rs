@@ -0,0 +1,238 @@
+use gpui::{
+    actions, px, AppContext, Element, IntoElement, Model, Pixels, Render, View, ViewContext,
+    VisualContext, WeakView, WindowBounds, WindowContext,
+};
+use project::{Project, RepositoryEntry};
+use recent_projects::RecentProjects;
+use std::env;
+use std::sync::OnceLock;
+use ui::{
+    h_flex, popover_menu, prelude::*, Button, ButtonStyle, IconButton, IconName, IconSize, Tooltip,
+};
+use util::ResultExt;
+use vcs_menu::{build_branch_list, BranchList, OpenRecent as ToggleVcsMenu};
+use workspace::Workspace;
+
+pub const MAX_PROJECT_NAME_LENGTH: usize = 40;
+pub const MAX_BRANCH_NAME_LENGTH: usize = 40;
+
+actions!(collab, [ToggleUserMenu, ToggleProjectMenu, SwitchBranch]);
+
+/// Registers `constructor` as the workspace's titlebar item, building a new
+/// one whenever a workspace is created. Every titlebar implementation shares
+/// this registration and only has to supply how its own view is built.
+pub fn init_titlebar_item<T, F>(cx: &mut AppContext, constructor: F)
+where
+    T: 'static + Render,
+    F: Fn(&Workspace, &mut ViewContext<T>) -> T + 'static,
+{
+    cx.observe_new_views(move |workspace: &mut Workspace, cx| {
+        let titlebar_item = cx.new_view(|cx| constructor(workspace, cx));
+        workspace.set_titlebar_item(titlebar_item.into(), cx)
+    })
+    .detach();
+}
+
+/// The macOS traffic lights are drawn by the OS at a fixed size and don't
+/// scale with the rest of the UI, so non-fullscreen windows need to reserve
+/// room for them on the leading edge. Other platforms draw no native
+/// decorations and get their own `render_window_controls` instead.
+pub const TRAFFIC_LIGHT_PADDING: Pixels = px(80.);
+
+/// Which edge of the titlebar `render_window_controls` should be placed on.
+/// Windows and most Linux desktop environments expect minimize/maximize/close
+/// on the trailing (right) edge, but some (e.g. elementary OS's Pantheon)
+/// expect them on the leading (left) edge instead.
+///
+/// [`TitleBar::window_controls_placement`] picks this via an `XDG_CURRENT_DESKTOP`
+/// check that is a best-effort guess, not something verified against a real
+/// Linux desktop-shell integration in this checkout (that layer isn't part
+/// of this tree). Treat it as a starting point to correct, not settled
+/// platform behavior.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowControlsPlacement {
+    Leading,
+    Trailing,
+}
+
+/// The project-name, branch, and recent-projects rendering shared by every
+/// titlebar implementation. Collaboration-specific sections (host, face
+/// pile, sharing controls) are composed on top of this by the crate that
+/// owns the particular titlebar variant.
+pub struct TitleBar {
+    project: Model<Project>,
+    workspace: WeakView<Workspace>,
+}
+
+impl TitleBar {
+    pub fn new(project: Model<Project>, workspace: WeakView<Workspace>) -> Self {
+        Self { project, workspace }
+    }
+
+    pub fn project(&self) -> &Model<Project> {
+        &self.project
+    }
+
+    pub fn workspace(&self) -> &WeakView<Workspace> {
+        &self.workspace
+    }
+
+    pub fn render_project_name(&self, cx: &mut WindowContext) -> impl Element {
+        let name = {
+            let mut names = self.project.read(cx).visible_worktrees(cx).map(|worktree| {
+                let worktree = worktree.read(cx);
+                worktree.root_name()
+            });
+
+            names.next()
+        };
+        let is_project_selected = name.is_some();
+        let name = if let Some(name) = name {
+            util::truncate_and_trailoff(name, MAX_PROJECT_NAME_LENGTH)
+        } else {
+            "Open recent project".to_string()
+        };
+
+        let workspace = self.workspace.clone();
+        popover_menu("project_name_trigger")
+            .trigger(
+                Button::new("project_name_trigger", name)
+                    .when(!is_project_selected, |b| b.color(Color::Muted))
+                    .style(ButtonStyle::Subtle)
+                    .label_size(LabelSize::Small)
+                    .tooltip(move |cx| Tooltip::text("Recent Projects", cx)),
+            )
+            .menu(move |cx| Some(Self::render_project_popover(workspace.clone(), cx)))
+    }
+
+    // STATUS: remote/dev-server branch support (rmchorgh/zedder#chunk0-6) is
+    // NOT implemented here — 0%. This only ever shows local branches and
+    // checks them out locally, exactly as before that request was filed.
+    // Remote-ref fetching, a "New branch..." action, and git-host-aware
+    // checkout/create routing all belong in
+    // `vcs_menu::build_branch_list`/`BranchList`, whose source isn't part of
+    // this checkout, so none of it can be written against real code here.
+    // Don't tag that request as done against this function; re-file it as
+    // open/blocked on vcs_menu until its source is available to change.
+    pub fn render_project_branch(&self, cx: &mut WindowContext) -> Option<impl Element> {
+        let entry = {
+            let mut names_and_branches =
+                self.project.read(cx).visible_worktrees(cx).map(|worktree| {
+                    let worktree = worktree.read(cx);
+                    worktree.root_git_entry()
+                });
+
+            names_and_branches.next().flatten()
+        };
+        let workspace = self.workspace.upgrade()?;
+        let branch_name = entry
+            .as_ref()
+            .and_then(RepositoryEntry::branch)
+            .map(|branch| util::truncate_and_trailoff(&branch, MAX_BRANCH_NAME_LENGTH))?;
+        Some(
+            popover_menu("project_branch_trigger")
+                .trigger(
+                    Button::new("project_branch_trigger", branch_name)
+                        .color(Color::Muted)
+                        .style(ButtonStyle::Subtle)
+                        .label_size(LabelSize::Small)
+                        .tooltip(move |cx| Tooltip::for_action("Recent Branches", &ToggleVcsMenu, cx)),
+                )
+                .menu(move |cx| Self::render_vcs_popover(workspace.clone(), cx)),
+        )
+    }
+
+    pub fn render_vcs_popover(
+        workspace: View<Workspace>,
+        cx: &mut WindowContext<'_>,
+    ) -> Option<View<BranchList>> {
+        let view = build_branch_list(workspace, cx).log_err()?;
+        let focus_handle = view.focus_handle(cx);
+        cx.focus(&focus_handle);
+        Some(view)
+    }
+
+    pub fn render_project_popover(
+        workspace: WeakView<Workspace>,
+        cx: &mut WindowContext<'_>,
+    ) -> View<RecentProjects> {
+        let view = RecentProjects::open_popover(workspace, cx);
+
+        let focus_handle = view.focus_handle(cx);
+        cx.focus(&focus_handle);
+        view
+    }
+
+    /// Returns `None` on macOS, which draws its own traffic lights; every
+    /// other platform gets the edge on which callers place the element from
+    /// [`Self::render_window_controls`].
+    pub fn window_controls_placement(_cx: &WindowContext) -> Option<WindowControlsPlacement> {
+        if cfg!(target_os = "macos") {
+            return None;
+        }
+
+        // `XDG_CURRENT_DESKTOP` is read once and cached: both titlebar
+        // variants call this on every render, and the desktop environment
+        // can't change without a restart.
+        static PLACEMENT: OnceLock<WindowControlsPlacement> = OnceLock::new();
+        Some(*PLACEMENT.get_or_init(|| {
+            // Best-effort guess, not verified against a real desktop-shell
+            // integration (that layer isn't part of this checkout): assume
+            // elementary OS's Pantheon desktop wants its window controls on
+            // the leading edge, and everything else on the trailing edge.
+            if cfg!(target_os = "linux")
+                && env::var("XDG_CURRENT_DESKTOP").is_ok_and(|desktop| desktop == "Pantheon")
+            {
+                WindowControlsPlacement::Leading
+            } else {
+                WindowControlsPlacement::Trailing
+            }
+        }))
+    }
+
+    /// macOS draws its own traffic lights, so the titlebar only needs to
+    /// render minimize/maximize/close controls everywhere else.
+    pub fn render_window_controls(&self, cx: &mut WindowContext) -> Option<impl Element> {
+        if cfg!(target_os = "macos") {
+            return None;
+        }
+
+        Some(
+            h_flex()
+                .id("window-controls")
+                .gap_1()
+                .child(self.render_minimize_button(cx))
+                .child(self.render_maximize_or_restore_button(cx))
+                .child(self.render_close_button(cx)),
+        )
+    }
+
+    fn render_minimize_button(&self, _cx: &mut WindowContext) -> impl Element {
+        IconButton::new("window-control-minimize", IconName::Minimize)
+            .style(ButtonStyle::Subtle)
+            .icon_size(IconSize::Small)
+            .on_click(|_, cx| cx.minimize_window())
+    }
+
+    fn render_maximize_or_restore_button(&self, cx: &mut WindowContext) -> impl Element {
+        let is_maximized = matches!(cx.window_bounds(), WindowBounds::Maximized);
+        IconButton::new(
+            "window-control-maximize",
+            if is_maximized {
+                IconName::Restore
+            } else {
+                IconName::Maximize
+            },
+        )
+        .style(ButtonStyle::Subtle)
+        .icon_size(IconSize::Small)
+        .on_click(|_, cx| cx.zoom_window())
+    }
+
+    fn render_close_button(&self, _cx: &mut WindowContext) -> impl Element {
+        IconButton::new("window-control-close", IconName::Close)
+            .style(ButtonStyle::Subtle)
+            .icon_size(IconSize::Small)
+            .on_click(|_, cx| cx.remove_window())
+    }
+}